@@ -1,27 +1,551 @@
-struct Greeting;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Languages with a known salutation data file (`salut-<name>`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Lang {
+    English,
+    French,
+    Italian,
+    Japanese,
+}
+
+impl Lang {
+    fn all() -> &'static [Lang] {
+        &[Lang::English, Lang::French, Lang::Italian, Lang::Japanese]
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Lang::English => "salut-english",
+            Lang::French => "salut-french",
+            Lang::Italian => "salut-italian",
+            Lang::Japanese => "salut-japanese",
+        }
+    }
+}
+
+/// The kind of occasion a salutation is used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Situation {
+    In,
+    Out,
+    Generic,
+    Morning,
+    Afternoon,
+    Evening,
+    Night,
+}
+
+impl Situation {
+    fn from_section_name(name: &str) -> Option<Situation> {
+        match name {
+            "in" => Some(Situation::In),
+            "out" => Some(Situation::Out),
+            "generic" => Some(Situation::Generic),
+            "morning" => Some(Situation::Morning),
+            "afternoon" => Some(Situation::Afternoon),
+            "evening" => Some(Situation::Evening),
+            "night" => Some(Situation::Night),
+            _ => None,
+        }
+    }
+
+    /// Maps a 24-hour clock hour (0-23) to the situation it falls under.
+    fn from_hour(hour: u16) -> Option<Situation> {
+        match hour {
+            5..=11 => Some(Situation::Morning),
+            12..=16 => Some(Situation::Afternoon),
+            17..=21 => Some(Situation::Evening),
+            22..=23 | 0..=4 => Some(Situation::Night),
+            _ => None,
+        }
+    }
+}
+
+type SalutationTable = HashMap<Situation, Vec<String>>;
+
+/// Parses a salutation data file into a table of situation -> phrases.
+///
+/// The format is simple line-based sections: a line of the form
+/// `[situation]` starts a section, and every following non-empty line is one
+/// alternative phrase for that situation, until the next section header.
+fn parse_salutation_file(contents: &str) -> SalutationTable {
+    let mut table: SalutationTable = HashMap::new();
+    let mut current: Option<Situation> = None;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            current = Situation::from_section_name(&line[1..line.len() - 1]);
+            continue;
+        }
+        if let Some(situation) = current {
+            table.entry(situation).or_default().push(line.to_string());
+        }
+    }
+    table
+}
+
+/// Loads every known language's salutation table from `dir`. Languages whose
+/// data file is missing or unreadable are simply absent from the result.
+fn load_salutations(dir: &Path) -> HashMap<Lang, SalutationTable> {
+    let mut tables = HashMap::new();
+    for &lang in Lang::all() {
+        let path = dir.join(lang.file_name());
+        if let Ok(contents) = fs::read_to_string(&path) {
+            tables.insert(lang, parse_salutation_file(&contents));
+        }
+    }
+    tables
+}
+
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// A pluggable source of greetings and farewells.
+///
+/// `Context` is whatever input a backend needs to produce a salutation (a
+/// name, a richer chat-message struct, ...), and `Output` is however that
+/// backend chooses to represent the result (a plain `String`, a structured
+/// record, ...). This lets callers like `run` work generically over any
+/// salutation source instead of being tied to one concrete struct.
+trait Salutation {
+    type Context: ?Sized;
+    type Output;
+
+    fn greet(&self, ctx: &Self::Context) -> Self::Output;
+    fn farewell(&self, ctx: &Self::Context) -> Self::Output;
+}
+
+/// The original, string-rendering greeter: loads salutation tables from
+/// disk and renders `{name}`-substituted phrases directly.
+struct SimpleGreeting {
+    lang: Lang,
+    all_languages: bool,
+    address_only: bool,
+    recipient_names: Vec<String>,
+    data_dir: PathBuf,
+    tables: RefCell<HashMap<Lang, SalutationTable>>,
+    mtimes: RefCell<HashMap<Lang, Option<SystemTime>>>,
+    counters: RefCell<HashMap<(Lang, Situation), usize>>,
+}
+
+impl SimpleGreeting {
+    /// Builds a greeter for `lang`, loading salutation tables from the
+    /// current directory.
+    fn new(lang: Lang) -> SimpleGreeting {
+        SimpleGreeting::with_data_dir(lang, PathBuf::from("."))
+    }
+
+    fn with_data_dir(lang: Lang, data_dir: PathBuf) -> SimpleGreeting {
+        let tables = load_salutations(&data_dir);
+        let mtimes = Lang::all()
+            .iter()
+            .map(|&l| (l, file_mtime(&data_dir.join(l.file_name()))))
+            .collect();
+        SimpleGreeting {
+            lang,
+            all_languages: false,
+            address_only: false,
+            recipient_names: Vec::new(),
+            data_dir,
+            tables: RefCell::new(tables),
+            mtimes: RefCell::new(mtimes),
+            counters: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// When enabled, phrases are drawn from every loaded language instead of
+    /// just `self.lang`.
+    fn set_all_languages(&mut self, all_languages: bool) {
+        self.all_languages = all_languages;
+    }
+
+    /// When enabled, `respond_to` only replies to messages addressed to one
+    /// of `recipient_names`.
+    fn set_address_only(&mut self, address_only: bool) {
+        self.address_only = address_only;
+    }
+
+    /// The names (the "generic-dest" list) that `respond_to` treats as
+    /// referring to this greeter when `address_only` is set.
+    fn set_recipient_names(&mut self, names: Vec<String>) {
+        self.recipient_names = names;
+    }
+
+    /// Reloads any language whose data file has changed on disk since it was
+    /// last read.
+    fn reload_if_changed(&self) {
+        let mut mtimes = self.mtimes.borrow_mut();
+        let mut tables = self.tables.borrow_mut();
+        for &lang in Lang::all() {
+            let path = self.data_dir.join(lang.file_name());
+            let current = file_mtime(&path);
+            if current != mtimes[&lang] {
+                if let Ok(contents) = fs::read_to_string(&path) {
+                    tables.insert(lang, parse_salutation_file(&contents));
+                } else {
+                    tables.remove(&lang);
+                }
+                mtimes.insert(lang, current);
+            }
+        }
+    }
+
+    /// Picks one phrase for `situation`, falling back to `Situation::Generic`
+    /// when the active language(s) have nothing for it, and cycling
+    /// round-robin through the alternatives so repeated calls don't always
+    /// return the same phrase.
+    fn pick_phrase(&self, situation: Situation) -> String {
+        self.reload_if_changed();
+        let tables = self.tables.borrow();
+        let langs: Vec<Lang> = if self.all_languages {
+            Lang::all().to_vec()
+        } else {
+            vec![self.lang]
+        };
+
+        let mut phrases: Vec<&String> = langs
+            .iter()
+            .filter_map(|l| tables.get(l).and_then(|t| t.get(&situation)))
+            .flat_map(|v| v.iter())
+            .collect();
+        if phrases.is_empty() {
+            phrases = langs
+                .iter()
+                .filter_map(|l| tables.get(l).and_then(|t| t.get(&Situation::Generic)))
+                .flat_map(|v| v.iter())
+                .collect();
+        }
+        if phrases.is_empty() {
+            // None of the active language(s) has a loaded phrase for this
+            // situation or for generic; fall back to the built-in default
+            // phrase so the greeter still works standalone.
+            return default_phrase(situation);
+        }
+
+        let mut counters = self.counters.borrow_mut();
+        let counter = counters.entry((self.lang, situation)).or_insert(0);
+        let chosen = phrases[*counter % phrases.len()].clone();
+        *counter += 1;
+        chosen
+    }
 
-impl Greeting {
     fn get_greeting(&self, name: &str) -> String {
-        format!("Hello, {}!", name)
+        render(&self.pick_phrase(Situation::In), name)
     }
 
     fn get_farewell(&self, name: &str) -> String {
-        format!("Goodbye, {}. Have a great day!", name)
+        render(&self.pick_phrase(Situation::Out), name)
+    }
+
+    /// Produces an hour-appropriate greeting for `name`. `hour` is a 24-hour
+    /// clock value (0-23); anything outside that range is rejected with
+    /// `None` instead of silently falling back to a generic greeting.
+    fn get_personalized_greeting(&self, name: &str, hour: u16) -> Option<String> {
+        let situation = Situation::from_hour(hour)?;
+        Some(render(&self.pick_phrase(situation), name))
     }
 
-    fn get_personalized_greeting(&self, name: &str, time_of_day: &str) -> String {
-        match time_of_day.to_lowercase().as_str() {
-            "morning" => format!("Good morning, {}", name),
-            "afternoon" => format!("Good afternoon, {}", name),
-            "evening" => format!("Good evening, {}", name),
-            _ => format!("Good day, {}", name),
+    /// Scans an incoming chat `message` for a known salutation trigger word
+    /// (hello/hi/bye in English, or the equivalent curated words of the
+    /// active language(s)) and, if `address_only` is set, requires the
+    /// message to begin or end with one of `recipient_names` (optionally
+    /// followed by punctuation). Returns the matching reply, or `None` if
+    /// the message isn't a salutation or isn't addressed to us.
+    fn respond_to(&self, message: &str) -> Option<String> {
+        let trimmed = message.trim();
+        if self.address_only && !self.is_addressed(trimmed) {
+            return None;
         }
+        let triggers = self.salutation_triggers();
+        let situation = trimmed
+            .split_whitespace()
+            .map(strip_punctuation)
+            .map(|w| w.to_lowercase())
+            .find_map(|word| {
+                triggers
+                    .iter()
+                    .find(|(trigger, _)| **trigger == word)
+                    .map(|(_, situation)| *situation)
+            })?;
+        Some(render(&self.pick_phrase(situation), "there"))
+    }
+
+    /// The salutation trigger words `respond_to` looks for: the curated
+    /// `language_triggers` of `self.lang`, or of every language when
+    /// `all_languages` is set.
+    fn salutation_triggers(&self) -> Vec<(&'static str, Situation)> {
+        let langs: Vec<Lang> = if self.all_languages {
+            Lang::all().to_vec()
+        } else {
+            vec![self.lang]
+        };
+        langs.iter().flat_map(|&l| language_triggers(l).iter().copied()).collect()
+    }
+
+    /// Whether `message` begins or ends with one of `recipient_names`
+    /// (case-insensitively, ignoring surrounding punctuation).
+    fn is_addressed(&self, message: &str) -> bool {
+        let words: Vec<&str> = message.split_whitespace().collect();
+        let (Some(&first), Some(&last)) = (words.first(), words.last()) else {
+            return false;
+        };
+        let first = strip_punctuation(first).to_lowercase();
+        let last = strip_punctuation(last).to_lowercase();
+        self.recipient_names
+            .iter()
+            .any(|name| { let name = name.to_lowercase(); name == first || name == last })
     }
 }
 
+/// A curated set of single "address/farewell word" triggers `respond_to`
+/// recognizes for `lang`, independent of the full (and much wordier)
+/// rendered salutation phrases, so a message like "quarterly earnings
+/// exceeded analyst expectations" is never mistaken for a greeting.
+fn language_triggers(lang: Lang) -> &'static [(&'static str, Situation)] {
+    match lang {
+        Lang::English => &[
+            ("hello", Situation::In),
+            ("hi", Situation::In),
+            ("bye", Situation::Out),
+            ("goodbye", Situation::Out),
+        ],
+        Lang::French => &[
+            ("bonjour", Situation::In),
+            ("salut", Situation::In),
+            ("revoir", Situation::Out),
+            ("bientôt", Situation::Out),
+        ],
+        Lang::Italian => &[
+            ("ciao", Situation::In),
+            ("salve", Situation::In),
+            ("arrivederci", Situation::Out),
+        ],
+        Lang::Japanese => &[
+            ("こんにちは", Situation::In),
+            ("やあ", Situation::In),
+            ("さようなら", Situation::Out),
+            ("またね", Situation::Out),
+        ],
+    }
+}
+
+/// Strips leading and trailing punctuation from a word, e.g. turning
+/// `"Bot,"` or `"\"hi!\""` into `"Bot"` / `"hi"`. Covers ASCII punctuation
+/// plus the full-width punctuation used by the Japanese salutation data.
+fn strip_punctuation(word: &str) -> String {
+    word.trim_matches(|c: char| c.is_ascii_punctuation() || matches!(c, '、' | '。' | '！' | '？'))
+        .to_string()
+}
+
+impl Salutation for SimpleGreeting {
+    type Context = str;
+    type Output = String;
+
+    fn greet(&self, ctx: &str) -> String {
+        self.get_greeting(ctx)
+    }
+
+    fn farewell(&self, ctx: &str) -> String {
+        self.get_farewell(ctx)
+    }
+}
+
+/// A salutation rendered for `name` (in `phrase`) together with the
+/// situation it was chosen for, for callers that want to inspect or log a salutation
+/// instead of just printing it.
+#[derive(Debug)]
+struct StructuredResult {
+    name: String,
+    situation: Situation,
+    phrase: String,
+}
+
+/// A salutation backend that wraps a `SimpleGreeting` but reports its
+/// results as `StructuredResult` instead of a plain `String`.
+struct StructuredGreeting {
+    inner: SimpleGreeting,
+}
+
+impl StructuredGreeting {
+    fn new(lang: Lang) -> StructuredGreeting {
+        StructuredGreeting { inner: SimpleGreeting::new(lang) }
+    }
+
+    fn structured(&self, name: &str, situation: Situation) -> StructuredResult {
+        let phrase = self.inner.pick_phrase(situation);
+        StructuredResult { name: name.to_string(), situation, phrase: render(&phrase, name) }
+    }
+}
+
+impl Salutation for StructuredGreeting {
+    type Context = str;
+    type Output = StructuredResult;
+
+    fn greet(&self, ctx: &str) -> StructuredResult {
+        self.structured(ctx, Situation::In)
+    }
+
+    fn farewell(&self, ctx: &str) -> StructuredResult {
+        self.structured(ctx, Situation::Out)
+    }
+}
+
+/// Drives any salutation backend through a greet-then-farewell cycle,
+/// printing whatever `Output` it produces. Demonstrates that callers can be
+/// generic over `Salutation` instead of depending on one concrete struct.
+fn run<S: Salutation>(backend: &S, ctx: &S::Context)
+where
+    S::Output: std::fmt::Debug,
+{
+    println!("{:?}", backend.greet(ctx));
+    println!("{:?}", backend.farewell(ctx));
+}
+
+/// English fallback used when none of the active language(s) has a loaded
+/// phrase for the situation or for generic.
+fn default_phrase(situation: Situation) -> String {
+    match situation {
+        Situation::In => "Hello, {name}!".to_string(),
+        Situation::Out => "Goodbye, {name}. Have a great day!".to_string(),
+        Situation::Morning => "Good morning, {name}".to_string(),
+        Situation::Afternoon => "Good afternoon, {name}".to_string(),
+        Situation::Evening => "Good evening, {name}".to_string(),
+        Situation::Night => "Good night, {name}".to_string(),
+        Situation::Generic => "Good day, {name}".to_string(),
+    }
+}
+
+/// Substitutes the `{name}` placeholder in `phrase`.
+fn render(phrase: &str, name: &str) -> String {
+    phrase.replace("{name}", name)
+}
+
 fn main() {
-    let greeting = Greeting;
+    let mut greeting = SimpleGreeting::new(Lang::English);
     println!("{}", greeting.get_greeting("foo"));
     println!("{}", greeting.get_farewell("bar"));
-    println!("{}", greeting.get_personalized_greeting("baz", "morning"));
+    match greeting.get_personalized_greeting("baz", 8) {
+        Some(message) => println!("{}", message),
+        None => println!("invalid hour"),
+    }
+
+    greeting.set_address_only(true);
+    greeting.set_recipient_names(vec!["Bot".to_string()]);
+    if let Some(reply) = greeting.respond_to("Bot, hello!") {
+        println!("{}", reply);
+    }
+    assert!(greeting.respond_to("hello everyone").is_none());
+    greeting.set_address_only(false);
+    // respond_to's triggers are a curated list of address/farewell words,
+    // not every word of every phrase, so unrelated chatter never matches.
+    assert!(greeting.respond_to("completely unrelated sentence about cats and dogs").is_none());
+    assert!(greeting.respond_to("the weather report says rain tomorrow").is_none());
+    assert!(greeting.respond_to("quarterly earnings exceeded analyst expectations").is_none());
+
+    exercise_respond_to_in_other_language();
+    exercise_all_languages_and_missing_language_fallback();
+    exercise_reload_on_change();
+    exercise_personalized_greeting_hour_bounds();
+
+    run(&greeting, "foo");
+
+    let structured = StructuredGreeting::new(Lang::English);
+    run(&structured, "foo");
+
+    // Unlike SimpleGreeting, StructuredGreeting lets callers read the name
+    // and situation behind a salutation alongside its rendered phrase, not
+    // just a plain string.
+    let greeted = structured.greet("foo");
+    assert_eq!(greeted.name, "foo");
+    assert_eq!(greeted.situation, Situation::In);
+    println!("chose \"{}\" for situation {:?}", greeted.phrase, greeted.situation);
+}
+
+/// Demonstrates that `respond_to`'s curated triggers come from the active
+/// language, not just English, using a fixture directory (rather than the
+/// process's current working directory) so the check is deterministic no
+/// matter where the binary is run from.
+fn exercise_respond_to_in_other_language() {
+    let dir = std::env::temp_dir().join("greeting-demo-french-respond-to");
+    fs::create_dir_all(&dir).expect("create demo data dir");
+    fs::write(dir.join("salut-french"), "[in]\nBonjour, {name} !\n[out]\nAu revoir, {name} !\n")
+        .expect("write demo data file");
+
+    let mut french = SimpleGreeting::with_data_dir(Lang::French, dir.clone());
+    french.set_address_only(true);
+    french.set_recipient_names(vec!["Bot".to_string()]);
+    assert!(french.respond_to("Bonjour, Bot!").is_some());
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+/// Demonstrates the missing-language fallback (a language with no data file
+/// falls back to the built-in default phrase) and `all_languages` mode
+/// (once enabled, a language loaded from disk is also searched).
+fn exercise_all_languages_and_missing_language_fallback() {
+    let dir = std::env::temp_dir().join("greeting-demo-all-languages");
+    fs::create_dir_all(&dir).expect("create demo data dir");
+    fs::write(dir.join("salut-french"), "[in]\nBonjour, {name} !\n").expect("write demo data file");
+
+    // English has no data file in this directory, so the English greeter
+    // falls back to its built-in default phrase...
+    let mut multi = SimpleGreeting::with_data_dir(Lang::English, dir.clone());
+    assert_eq!(multi.get_greeting("Zoe"), "Hello, Zoe!");
+
+    // ...but with all_languages enabled, the French table loaded from disk
+    // is searched too.
+    multi.set_all_languages(true);
+    assert_eq!(multi.get_greeting("Zoe"), "Bonjour, Zoe !");
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+/// Demonstrates `get_personalized_greeting`'s hour handling at every
+/// `Situation::from_hour` boundary: every valid hour (0-23) yields a
+/// greeting, and the first hour outside the 24-hour clock (24) yields
+/// `None` instead of a generic fallback.
+fn exercise_personalized_greeting_hour_bounds() {
+    let dir = std::env::temp_dir().join("greeting-demo-hour-bounds");
+    fs::create_dir_all(&dir).expect("create demo data dir");
+    let greeting = SimpleGreeting::with_data_dir(Lang::English, dir.clone());
+
+    for hour in 0..=23u16 {
+        assert!(
+            greeting.get_personalized_greeting("Zoe", hour).is_some(),
+            "expected a greeting for hour {hour}"
+        );
+    }
+
+    assert!(greeting.get_personalized_greeting("Zoe", 24).is_none());
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+/// Demonstrates that a greeter notices when its salutation file changes on
+/// disk after construction and reloads it on the next lookup.
+fn exercise_reload_on_change() {
+    let dir = std::env::temp_dir().join("greeting-demo-reload");
+    fs::create_dir_all(&dir).expect("create demo data dir");
+    let file = dir.join("salut-english");
+    fs::write(&file, "[in]\nYo, {name}!\n").expect("write demo data file");
+
+    let greeter = SimpleGreeting::with_data_dir(Lang::English, dir.clone());
+    assert_eq!(greeter.get_greeting("Zoe"), "Yo, Zoe!");
+
+    // Bump the mtime forward so the change is observed even on filesystems
+    // with second-granularity mtimes.
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    fs::write(&file, "[in]\nHowdy, {name}!\n").expect("rewrite demo data file");
+    assert_eq!(greeter.get_greeting("Zoe"), "Howdy, Zoe!");
+
+    fs::remove_dir_all(&dir).ok();
 }